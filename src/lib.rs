@@ -23,17 +23,59 @@ use url::Url;
 /// // let invalid_url = url!("not a valid url");
 /// ```
 ///
+/// A second, comma-separated literal is resolved against the first as a base URL, the same
+/// way [`Url::join`] would at runtime, except the join happens at compile time:
+///
+/// ```rust
+/// use url_macro::url;
+///
+/// let resolved = url!("https://api.example.com/v1/", "users/42");
+/// assert_eq!(resolved.as_str(), "https://api.example.com/v1/users/42");
+/// ```
+///
+/// A builder form assembles a URL from named components instead of a single literal.
+/// `scheme` and `host` are required; `port`, `path`, `query` and `fragment` are optional:
+///
+/// ```rust
+/// use url_macro::url;
+///
+/// let built = url!(
+///     scheme = "https",
+///     host = "example.com",
+///     port = 8080,
+///     path = ["a", "b"],
+///     query = [("k", "v"), ("x", "y")],
+///     fragment = "frag"
+/// );
+/// assert_eq!(built.as_str(), "https://example.com:8080/a/b?k=v&x=y#frag");
+/// ```
+///
 /// # Features
 ///
 /// - Validates URLs at compile-time, preventing runtime errors from malformed URLs.
 /// - Provides early error detection in the development process.
 /// - Automatically converts valid URL strings into `url::Url` objects.
 /// - Preserves the original span information for precise error reporting.
+/// - Resolves a relative URL against a base URL at compile time when given two literals.
+/// - Assembles a URL from named `scheme`/`host`/`port`/`path`/`query`/`fragment` components.
+///
+/// Regular strings, raw strings (`r"..."`, `r#"..."#`) and `concat!` of string literals are all
+/// accepted wherever a URL string is expected:
+///
+/// ```rust
+/// use url_macro::url;
+///
+/// let raw = url!(r"https://example.com/raw");
+/// let concatenated = url!(concat!("https://", "example.com"));
+/// assert_eq!(concatenated.as_str(), "https://example.com/");
+/// ```
 ///
 /// # Limitations
 ///
-/// - The macro only accepts string literals. Variables or expressions that evaluate to strings
-///   at runtime cannot be used with this macro.
+/// - The macro only accepts literals: string literals (including raw strings and `concat!` of
+///   string literals), plus an integer literal for the builder form's `port`. Variables or
+///   expressions that evaluate to strings at runtime cannot be used with this macro. Byte
+///   string literals (`b"..."`) are rejected.
 ///
 /// # Dependencies
 ///
@@ -72,45 +114,427 @@ use url::Url;
 /// # See Also
 ///
 /// - The [`url`](https://docs.rs/url) crate documentation for more information on URL parsing and manipulation.
+/// - [url_str!] for a version that yields a `&'static str` at zero runtime cost.
 #[proc_macro]
 pub fn url(input: TokenStream) -> TokenStream {
     url_result(input).unwrap_or_else(identity)
 }
 
+/// A compile-time URL validation macro that yields the normalized URL as a `&'static str`.
+///
+/// This is a sibling of [url!] for callers who only need the canonical string form (logging,
+/// header values, map keys) and want to avoid paying for a `url::Url` allocation at runtime.
+/// `Url::parse` can't be `const`, so [url!] still allocates and re-parses on every expansion;
+/// `url_str!` does all of that validation at compile time and emits the already-normalized
+/// string literal, at zero runtime cost. Callers who need an owned `Url` can parse the string
+/// lazily themselves.
+///
+/// It accepts the exact same input forms as [url!] (single literal, base + relative, the
+/// named-component builder, raw strings, and `concat!`) and produces the same normalized
+/// string that `Url::parse(input).unwrap().as_str()` would (lowercased host, default ports
+/// stripped, path normalized).
+///
+/// # Examples
+///
+/// ```rust
+/// use url_macro::url_str;
+///
+/// const GITHUB: &str = url_str!("https://GitHub.com");
+/// assert_eq!(GITHUB, "https://github.com/");
+/// ```
+#[proc_macro]
+pub fn url_str(input: TokenStream) -> TokenStream {
+    url_str_result(input).unwrap_or_else(identity)
+}
+
 fn url_result(input: TokenStream) -> Result<TokenStream, TokenStream> {
+    let (url_str, span) = url_value(input)?;
+    let literal = Literal::string(&url_str);
+    let result = format!("::url::Url::parse({}).unwrap()", literal);
+    result
+        .parse()
+        .map_err(|err: LexError| to_compile_error_stream(&err.to_string(), span))
+}
+
+fn url_str_result(input: TokenStream) -> Result<TokenStream, TokenStream> {
+    let (url_str, span) = url_value(input)?;
+    let mut literal = Literal::string(&url_str);
+    literal.set_span(span);
+    Ok(TokenStream::from_iter([TokenTree::Literal(literal)]))
+}
+
+/// Parses and validates the macro input, returning the normalized URL string and a span to
+/// attach to the generated tokens and to report further compile-time errors against (e.g. a
+/// failure to tokenize the generated code).
+fn url_value(input: TokenStream) -> Result<(String, Span), TokenStream> {
+    let mut tokens = input.into_iter().peekable();
+
     // Get the first token
-    let token = input
-        .into_iter()
+    let first = tokens
         .next()
-        .ok_or_else(|| to_compile_error_stream("Expected a string literal", Span::call_site()))?;
+        .ok_or_else(|| to_compile_error_stream("Expected a string literal or a `name = value` field", Span::call_site()))?;
+
+    // An `ident =` pair at the start means the builder form; otherwise it's the literal form.
+    let is_builder_form =
+        matches!(&first, TokenTree::Ident(_)) && matches!(tokens.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '=');
+
+    if is_builder_form {
+        return builder_url(first, tokens);
+    }
+
+    let (base_str, base_span) = take_string_value(first, &mut tokens)?;
+
+    match tokens.next() {
+        // Single-argument form: `url!("...")`.
+        None => single_url(base_str, base_span),
+        // Two-argument form: `url!("base", "relative")`.
+        Some(TokenTree::Punct(comma)) if comma.as_char() == ',' => {
+            let rel_token = tokens
+                .next()
+                .ok_or_else(|| to_compile_error_stream("Expected a relative URL string literal after ','", comma.span()))?;
+            let (rel_str, rel_span) = take_string_value(rel_token, &mut tokens)?;
 
-    // Ensure it's a string literal
-    let literal = match token {
+            // Allow (and require) nothing but an optional trailing comma after that.
+            if let Some(extra) = tokens.next() {
+                let is_trailing_comma = matches!(&extra, TokenTree::Punct(p) if p.as_char() == ',');
+                if !is_trailing_comma || tokens.next().is_some() {
+                    return Err(to_compile_error_stream("Unexpected extra tokens after relative URL literal", extra.span()));
+                }
+            }
+
+            joined_url(base_str, base_span, rel_str, rel_span)
+        }
+        Some(other) => Err(to_compile_error_stream("Expected ',' after base URL literal", other.span())),
+    }
+}
+
+fn expect_literal(token: TokenTree) -> Result<Literal, TokenStream> {
+    match token {
         TokenTree::Literal(lit) => Ok(lit),
-        _ => Err(to_compile_error_stream("Expected a string literal", Span::call_site())),
-    }?;
+        other => Err(to_compile_error_stream("Expected an integer literal", other.span())),
+    }
+}
 
+/// Consumes a string value starting at `token`: a string literal (regular or raw), or a
+/// `concat!(...)` invocation of string literals, which consumes further tokens from `tokens`.
+/// Rejects byte strings and any other literal kind.
+fn take_string_value(token: TokenTree, tokens: &mut impl Iterator<Item = TokenTree>) -> Result<(String, Span), TokenStream> {
+    match token {
+        TokenTree::Literal(literal) => {
+            let span = literal.span();
+            Ok((parse_string_literal(&literal)?, span))
+        }
+        TokenTree::Ident(ident) if ident.to_string() == "concat" => {
+            let span = ident.span();
+
+            match tokens.next() {
+                Some(TokenTree::Punct(p)) if p.as_char() == '!' => {}
+                Some(other) => return Err(to_compile_error_stream("Expected '!' after `concat`", other.span())),
+                None => return Err(to_compile_error_stream("Expected '!' after `concat`", span)),
+            }
+
+            let group = match tokens.next() {
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => group,
+                Some(other) => return Err(to_compile_error_stream("Expected '(' after `concat!`", other.span())),
+                None => return Err(to_compile_error_stream("Expected '(' after `concat!`", span)),
+            };
+
+            let mut result = String::new();
+            for item in split_items(group.stream())? {
+                let (piece, _) = value_from_stream(item, group.span())?;
+                result.push_str(&piece);
+            }
+            Ok((result, span))
+        }
+        other => Err(to_compile_error_stream("Expected a string literal", other.span())),
+    }
+}
+
+/// Parses a self-contained value out of its own `TokenStream`, erroring on leftover tokens.
+fn value_from_stream(stream: TokenStream, context_span: Span) -> Result<(String, Span), TokenStream> {
+    let mut tokens = stream.into_iter();
+    let first = tokens
+        .next()
+        .ok_or_else(|| to_compile_error_stream("Expected a string literal", context_span))?;
+    let (value, span) = take_string_value(first, &mut tokens)?;
+
+    if let Some(extra) = tokens.next() {
+        return Err(to_compile_error_stream("Unexpected extra tokens", extra.span()));
+    }
+
+    Ok((value, span))
+}
+
+/// Parses the string value of a literal, unescaping regular and raw strings.
+/// Byte strings and non-string literals are rejected with a `compile_error!`.
+fn parse_string_literal(literal: &Literal) -> Result<String, TokenStream> {
     let span = literal.span();
+    let text = literal.to_string();
 
-    // Extract the string value
-    let url_str = literal.to_string();
+    if text.starts_with("b\"") || text.starts_with("br\"") || text.starts_with("br#") {
+        return Err(to_compile_error_stream("Expected a string literal, found a byte string literal", span));
+    }
 
-    // Remove the surrounding quotes
-    let url_str = url_str.trim_matches('"');
+    if let Some(rest) = text.strip_prefix('r') {
+        let hash_count = rest.chars().take_while(|&c| c == '#').count();
+        let body = &rest[hash_count..];
+        let is_well_formed = body.starts_with('"') && body.len() > 1 + hash_count && body.ends_with(&"#".repeat(hash_count));
+        if !is_well_formed {
+            return Err(to_compile_error_stream("Expected a string literal", span));
+        }
+        return Ok(body[1..body.len() - hash_count - 1].to_string());
+    }
+
+    if text.starts_with('"') && text.ends_with('"') && text.len() >= 2 {
+        return unescape_string(&text[1..text.len() - 1], span);
+    }
+
+    Err(to_compile_error_stream("Expected a string literal", span))
+}
+
+/// Resolves the escape sequences of a regular (non-raw) string literal's inner text.
+fn unescape_string(inner: &str, span: Span) -> Result<String, TokenStream> {
+    let mut chars = inner.chars().peekable();
+    let mut out = String::with_capacity(inner.len());
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
 
-    // Parse the URL
-    match Url::parse(url_str) {
-        Ok(_) => {
-            // If parsing succeeds, output the unwrap code
-            let result = format!("::url::Url::parse({}).unwrap()", literal);
-            result
-                .parse()
-                .map_err(|err: LexError| to_compile_error_stream(&err.to_string(), span))
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('t') => out.push('\t'),
+            Some('\\') => out.push('\\'),
+            Some('"') => out.push('"'),
+            Some('\'') => out.push('\''),
+            Some('0') => out.push('\0'),
+            Some('x') => {
+                let hex: String = chars.by_ref().take(2).collect();
+                let byte = u8::from_str_radix(&hex, 16).map_err(|_| to_compile_error_stream("Invalid \\x escape", span))?;
+                out.push(byte as char);
+            }
+            Some('u') => {
+                if chars.next() != Some('{') {
+                    return Err(to_compile_error_stream("Expected '{' after \\u", span));
+                }
+                let mut hex = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => hex.push(c),
+                        None => return Err(to_compile_error_stream("Unterminated \\u escape", span)),
+                    }
+                }
+                let code = u32::from_str_radix(&hex, 16).map_err(|_| to_compile_error_stream("Invalid \\u escape", span))?;
+                let ch = char::from_u32(code).ok_or_else(|| to_compile_error_stream("Invalid \\u escape", span))?;
+                out.push(ch);
+            }
+            // Line continuation: a backslash-newline strips the newline and any leading whitespace.
+            Some('\n') => {
+                while matches!(chars.peek(), Some(c) if c.is_whitespace()) {
+                    chars.next();
+                }
+            }
+            Some(other) => return Err(to_compile_error_stream(&format!("Unknown escape sequence '\\{other}'"), span)),
+            None => return Err(to_compile_error_stream("Unterminated escape sequence", span)),
         }
+    }
+
+    Ok(out)
+}
+
+fn single_url(url_str: String, span: Span) -> Result<(String, Span), TokenStream> {
+    match Url::parse(&url_str) {
+        Ok(parsed) => Ok((parsed.as_str().to_string(), span)),
         Err(err) => Err(to_compile_error_stream(&err.to_string(), span)),
     }
 }
 
+fn joined_url(base_str: String, base_span: Span, rel_str: String, rel_span: Span) -> Result<(String, Span), TokenStream> {
+    let base = Url::parse(&base_str).map_err(|err| to_compile_error_stream(&format!("Invalid base URL: {err}"), base_span))?;
+
+    if base.cannot_be_a_base() {
+        return Err(to_compile_error_stream(
+            "The base URL cannot be a base (e.g. `data:` or `mailto:` URLs have no hierarchical path to join against)",
+            base_span,
+        ));
+    }
+
+    let joined = base
+        .join(&rel_str)
+        .map_err(|err| to_compile_error_stream(&format!("Failed to join relative URL onto base: {err}"), rel_span))?;
+
+    Ok((joined.as_str().to_string(), rel_span))
+}
+
+#[derive(Default)]
+struct BuilderFields {
+    scheme: Option<String>,
+    host: Option<String>,
+    port: Option<u16>,
+    path: Vec<String>,
+    query: Vec<(String, String)>,
+    fragment: Option<String>,
+}
+
+/// Builder form: `url!(scheme = "https", host = "example.com", ...)`.
+fn builder_url(first_ident: TokenTree, tokens: impl Iterator<Item = TokenTree>) -> Result<(String, Span), TokenStream> {
+    let mut tokens = tokens;
+    let mut fields = BuilderFields::default();
+    let mut current_ident = Some(first_ident);
+
+    while let Some(ident_token) = current_ident.take() {
+        let ident_span = ident_token.span();
+        let name = match &ident_token {
+            TokenTree::Ident(ident) => ident.to_string(),
+            other => return Err(to_compile_error_stream("Expected a field name", other.span())),
+        };
+
+        match tokens.next() {
+            Some(TokenTree::Punct(p)) if p.as_char() == '=' => {}
+            Some(other) => return Err(to_compile_error_stream(&format!("Expected '=' after `{name}`"), other.span())),
+            None => return Err(to_compile_error_stream(&format!("Expected '=' after `{name}`"), ident_span)),
+        }
+
+        let value_token = tokens
+            .next()
+            .ok_or_else(|| to_compile_error_stream(&format!("Expected a value for `{name}`"), ident_span))?;
+        let value_span = value_token.span();
+
+        match name.as_str() {
+            "scheme" => fields.scheme = Some(take_string_value(value_token, &mut tokens)?.0),
+            "host" => fields.host = Some(take_string_value(value_token, &mut tokens)?.0),
+            "port" => {
+                let literal = expect_literal(value_token)?;
+                let port = literal
+                    .to_string()
+                    .parse::<u16>()
+                    .map_err(|err| to_compile_error_stream(&format!("Invalid port: {err}"), value_span))?;
+                fields.port = Some(port);
+            }
+            "path" => fields.path = parse_string_list(expect_bracket_group(value_token)?)?,
+            "query" => fields.query = parse_query_list(expect_bracket_group(value_token)?)?,
+            "fragment" => fields.fragment = Some(take_string_value(value_token, &mut tokens)?.0),
+            other => return Err(to_compile_error_stream(&format!("Unknown field `{other}`"), ident_span)),
+        }
+
+        current_ident = match tokens.next() {
+            None => None,
+            Some(TokenTree::Punct(p)) if p.as_char() == ',' => tokens.next(),
+            Some(other) => return Err(to_compile_error_stream("Expected ',' between fields", other.span())),
+        };
+    }
+
+    let scheme = fields
+        .scheme
+        .ok_or_else(|| to_compile_error_stream("Missing required field `scheme`", Span::call_site()))?;
+    let host = fields
+        .host
+        .ok_or_else(|| to_compile_error_stream("Missing required field `host`", Span::call_site()))?;
+
+    let mut url = Url::parse(&format!("{scheme}://{host}"))
+        .map_err(|err| to_compile_error_stream(&format!("Invalid `scheme`/`host`: {err}"), Span::call_site()))?;
+
+    if let Some(port) = fields.port {
+        url.set_port(Some(port))
+            .map_err(|()| to_compile_error_stream("This scheme does not support a `port`", Span::call_site()))?;
+    }
+
+    if !fields.path.is_empty() {
+        url.path_segments_mut()
+            .map_err(|()| to_compile_error_stream("This scheme does not support a `path`", Span::call_site()))?
+            .clear()
+            .extend(fields.path.iter().map(String::as_str));
+    }
+
+    if !fields.query.is_empty() {
+        url.query_pairs_mut()
+            .extend_pairs(fields.query.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+    }
+
+    if let Some(fragment) = fields.fragment {
+        url.set_fragment(Some(&fragment));
+    }
+
+    Ok((url.as_str().to_string(), Span::call_site()))
+}
+
+/// Splits a token stream into its top-level comma-separated items, allowing a trailing comma.
+/// Each item is itself a (possibly multi-token) `TokenStream`, e.g. a `concat!(...)` call.
+fn split_items(stream: TokenStream) -> Result<Vec<TokenStream>, TokenStream> {
+    let mut items = Vec::new();
+    let mut current = Vec::new();
+
+    for token in stream {
+        match &token {
+            TokenTree::Punct(p) if p.as_char() == ',' => {
+                items.push(TokenStream::from_iter(current.drain(..)));
+            }
+            _ => current.push(token),
+        }
+    }
+
+    if !current.is_empty() {
+        items.push(TokenStream::from_iter(current));
+    }
+
+    Ok(items)
+}
+
+fn expect_bracket_group(token: TokenTree) -> Result<Group, TokenStream> {
+    match token {
+        TokenTree::Group(group) if group.delimiter() == Delimiter::Bracket => Ok(group),
+        other => Err(to_compile_error_stream("Expected a `[...]` list", other.span())),
+    }
+}
+
+fn parse_string_list(group: Group) -> Result<Vec<String>, TokenStream> {
+    split_items(group.stream())?
+        .into_iter()
+        .map(|item| value_from_stream(item, group.span()).map(|(value, _)| value))
+        .collect()
+}
+
+fn parse_query_list(group: Group) -> Result<Vec<(String, String)>, TokenStream> {
+    split_items(group.stream())?
+        .into_iter()
+        .map(|item| {
+            let mut item_tokens = item.into_iter();
+            let pair_group = match item_tokens.next() {
+                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => group,
+                Some(other) => return Err(to_compile_error_stream("Expected a `(key, value)` tuple", other.span())),
+                None => return Err(to_compile_error_stream("Expected a `(key, value)` tuple", group.span())),
+            };
+            if let Some(extra) = item_tokens.next() {
+                return Err(to_compile_error_stream("Unexpected extra tokens after query tuple", extra.span()));
+            }
+
+            let pair_span = pair_group.span();
+            let mut items = split_items(pair_group.stream())?.into_iter();
+
+            let key = items
+                .next()
+                .ok_or_else(|| to_compile_error_stream("Expected a query key", pair_span))
+                .and_then(|item| value_from_stream(item, pair_span))?
+                .0;
+            let value = items
+                .next()
+                .ok_or_else(|| to_compile_error_stream("Expected a query value", pair_span))
+                .and_then(|item| value_from_stream(item, pair_span))?
+                .0;
+
+            if items.next().is_some() {
+                return Err(to_compile_error_stream("Expected exactly two elements in query tuple", pair_span));
+            }
+
+            Ok((key, value))
+        })
+        .collect()
+}
+
 fn to_compile_error_stream(message: &str, span: Span) -> TokenStream {
     TokenStream::from_iter([
         TokenTree::Punct({